@@ -19,17 +19,51 @@ r#"Usage: slice [OPTION]... [FILE]
 Print slice from FILE to standard output.
 When slice is not specified, print whole file to standard output.
 
-    -s, --slice BEGIN:END       specify slice to print
-    -h, --help                  display this help and exit
-    -v, --version               output version information and exit
-    --                          end of options
+    -s, --slice SLICE[,SLICE...] specify line slice(s) to print
+    -c, --chars BEGIN:END[:STEP] specify slice of Unicode scalar values to print
+    -b, --bytes BEGIN:END[:STEP] specify slice of raw bytes to print
+    -p, --preserve               preserve original line terminators (line mode only)
+    -f, --fields                 split each selected line into fields and apply
+                                 --field-slice to them (line mode only)
+    -d, --delimiter DELIM        field delimiter for -f (defaults to a tab
+                                 character)
+        --field-slice SLICE      BEGIN:END[:STEP] spec selecting which fields
+                                 -f keeps
+    -h, --help                   display this help and exit
+    -v, --version                output version information and exit
+    --                           end of options
 
-BEGIN and END may be any combination of positive (denoting position
-from the beginning) or negative (denoting position from the end) numbers.
+-s, -c and -b are mutually exclusive; whichever is given last wins.
 
-Both LF and CRLF are recognized as newline characters.
-Newlines are not preserved and are always replaced with LF in output.
-Last line of the output will always end with LF."#
+Each SLICE is a BEGIN:END[:STEP] spec. BEGIN and END may be any
+combination of positive (denoting position from the beginning) or
+negative (denoting position from the end) numbers.
+
+STEP is optional and defaults to 1. A positive STEP keeps every STEP-th
+element of the range; a negative STEP reverses the range, emitting every
+|STEP|-th element starting from its last one. STEP cannot be 0.
+
+-s accepts a comma-separated list of SLICEs; the union of their selected
+lines is printed in input order, with overlaps collapsed. -c and -b take
+a single SLICE.
+
+In line mode (-s, the default), both LF and CRLF are recognized as
+newline characters. By default newlines are not preserved and are always
+replaced with LF in output, with the last line always ending with LF.
+Pass -p/--preserve to keep each line's original terminator (LF, CRLF, or
+none for a final line that had no trailing newline) instead.
+
+In char and byte mode (-c, -b), the input is treated as a single flat
+sequence and emitted as-is, with no newline normalization.
+
+-f/--fields applies only in line mode. Each surviving line is split on
+the delimiter (a tab by default, or whatever -d/--delimiter gives) and
+--field-slice is resolved against its fields the same way a line SLICE
+is resolved against lines, including negative indices; the selected
+fields are rejoined with the delimiter. Field counts vary per line, so
+a field slice that falls outside a given line's fields prints that line
+as empty rather than failing. --field-slice and -d/--delimiter have no
+effect unless -f is also given."#
     );
     process::exit(0);
 }
@@ -44,9 +78,19 @@ fn version() -> ! {
     process::exit(0);
 }
 
+enum Mode {
+    Lines(SliceSet),
+    Chars(Slice),
+    Bytes(Slice)
+}
+
 fn main() {
-    let mut slice = Slice{ begin: 0, end: None };
+    let mut mode = Mode::Lines(SliceSet{ slices: vec![Slice{ begin: 0, end: None, step: None }] });
     let mut filename: Option<String> = None;
+    let mut preserve = false;
+    let mut fields_enabled = false;
+    let mut field_delim = String::from("\t");
+    let mut field_slice: Slice = Slice{ begin: 0, end: None, step: None };
 
     let mut named_args = true;
     let mut args_iter = env::args().into_iter().skip(1); // skip executable name
@@ -60,9 +104,42 @@ fn main() {
                 } else if named_args && (arg == "-v" || arg == "--version") {
                     version();
                 } else if named_args && (arg == "-s" || arg == "--slice") {
+                    match args_iter.next() {
+                        Some(next_arg) => match SliceSet::from_string(&next_arg) {
+                            Ok(parsed_slice_set) => mode = Mode::Lines(parsed_slice_set),
+                            Err(error) => fail!("Failed to parse slice \"{}\": {}", next_arg, error)
+                        },
+                        None => fail!("\"{}\" option provided without argument.", arg)
+                    }
+                } else if named_args && (arg == "-c" || arg == "--chars") {
                     match args_iter.next() {
                         Some(next_arg) => match Slice::from_string(&next_arg) {
-                            Ok(parsed_slice) => slice = parsed_slice,
+                            Ok(parsed_slice) => mode = Mode::Chars(parsed_slice),
+                            Err(error) => fail!("Failed to parse slice \"{}\": {}", next_arg, error)
+                        },
+                        None => fail!("\"{}\" option provided without argument.", arg)
+                    }
+                } else if named_args && (arg == "-b" || arg == "--bytes") {
+                    match args_iter.next() {
+                        Some(next_arg) => match Slice::from_string(&next_arg) {
+                            Ok(parsed_slice) => mode = Mode::Bytes(parsed_slice),
+                            Err(error) => fail!("Failed to parse slice \"{}\": {}", next_arg, error)
+                        },
+                        None => fail!("\"{}\" option provided without argument.", arg)
+                    }
+                } else if named_args && (arg == "-p" || arg == "--preserve") {
+                    preserve = true;
+                } else if named_args && (arg == "-f" || arg == "--fields") {
+                    fields_enabled = true;
+                } else if named_args && (arg == "-d" || arg == "--delimiter") {
+                    match args_iter.next() {
+                        Some(next_arg) => field_delim = next_arg,
+                        None => fail!("\"{}\" option provided without argument.", arg)
+                    }
+                } else if named_args && arg == "--field-slice" {
+                    match args_iter.next() {
+                        Some(next_arg) => match Slice::from_string(&next_arg) {
+                            Ok(parsed_slice) => field_slice = parsed_slice,
                             Err(error) => fail!("Failed to parse slice \"{}\": {}", next_arg, error)
                         },
                         None => fail!("\"{}\" option provided without argument.", arg)
@@ -89,7 +166,17 @@ fn main() {
         input = Box::new(stdin.lock());
     }
 
-    if let Err(error) = slice_input(slice, &mut input, &mut io::stdout().lock()) {
+    let fields = if fields_enabled { Some((&field_slice, field_delim.as_str())) } else { None };
+
+    let result = match mode {
+        Mode::Lines(mut set) if set.slices.len() == 1 =>
+            slice_input(set.slices.remove(0), &mut input, &mut io::stdout().lock(), preserve, fields),
+        Mode::Lines(set) => slice_input_set(set.slices, &mut input, &mut io::stdout().lock(), preserve, fields),
+        Mode::Chars(slice) => slice_input_chars(slice, &mut input, &mut io::stdout().lock()),
+        Mode::Bytes(slice) => slice_input_bytes(slice, &mut input, &mut io::stdout().lock())
+    };
+
+    if let Err(error) = result {
         fail!("Failed to perform slice: {}", error);
     }
 }
\ No newline at end of file
@@ -1,26 +1,25 @@
+use std::borrow::Cow;
 use std::cmp;
-use std::collections::VecDeque;
+use std::collections::{BTreeSet, VecDeque};
 use std::fmt;
 use std::io::{self, BufRead, Write};
 
 #[derive(Debug, PartialEq)]
 pub struct Slice {
     pub begin: isize,
-    pub end: Option<isize>
+    pub end: Option<isize>,
+    pub step: Option<isize>
 }
 
 impl Slice {
     pub fn from_string(slice_str: &str) -> Result<Slice, &'static str> {
         let parts: Vec<&str> = slice_str.split(':').collect();
 
-        if parts.len() != 2 {
+        if parts.len() < 2 || parts.len() > 3 {
             return Err("Invalid slice");
         }
-        if parts[0].is_empty() && parts[1].is_empty() {
-            return Err("Slice cannot be empty");
-        }
 
-        let mut slice = Slice{ begin: 0, end: None };
+        let mut slice = Slice{ begin: 0, end: None, step: None };
         if !parts[0].is_empty() {
             match parts[0].parse::<isize>() {
                 Ok(begin_value) => slice.begin = begin_value,
@@ -33,27 +32,156 @@ impl Slice {
                 Err(_) => return Err("Invalid slice ending point")
             }
         }
+        if parts.len() == 3 {
+            if parts[2].is_empty() {
+                return Err("Invalid slice step");
+            }
+            match parts[2].parse::<isize>() {
+                Ok(0) => return Err("Slice step cannot be zero"),
+                Ok(step_value) => slice.step = Some(step_value),
+                Err(_) => return Err("Invalid slice step")
+            }
+        }
+
+        if parts[0].is_empty() && parts[1].is_empty() && slice.step.is_none() {
+            return Err("Slice cannot be empty");
+        }
+
         return Ok(slice);
     }
 }
 
 impl fmt::Display for Slice {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.end {
-            Some(end_v) => write!(f, "[{}:{}]", self.begin, end_v),
-            None => write!(f, "[{}:]", self.begin)
+        match (self.end, self.step) {
+            (Some(end_v), Some(step_v)) => write!(f, "[{}:{}:{}]", self.begin, end_v, step_v),
+            (Some(end_v), None) => write!(f, "[{}:{}]", self.begin, end_v),
+            (None, Some(step_v)) => write!(f, "[{}::{}]", self.begin, step_v),
+            (None, None) => write!(f, "[{}:]", self.begin)
+        }
+    }
+}
+
+/// A comma-separated list of `Slice`s, e.g. "1:3,5:7,-2:".
+#[derive(Debug, PartialEq)]
+pub struct SliceSet {
+    pub slices: Vec<Slice>
+}
+
+impl SliceSet {
+    pub fn from_string(slice_set_str: &str) -> Result<SliceSet, &'static str> {
+        let slices = slice_set_str
+            .split(',')
+            .map(Slice::from_string)
+            .collect::<Result<Vec<Slice>, &'static str>>()?;
+
+        Ok(SliceSet{ slices })
+    }
+}
+
+// Which line terminator (if any) followed a line as it appeared in the input.
+#[derive(Debug, PartialEq)]
+enum Terminator {
+    Lf,
+    CrLf,
+    None
+}
+
+impl Terminator {
+    fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            Terminator::Lf => b"\n",
+            Terminator::CrLf => b"\r\n",
+            Terminator::None => b""
         }
     }
 }
 
-pub fn slice_input(slice: Slice, input: &mut dyn BufRead, output: &mut dyn Write) -> io::Result<()> {
+// Like `BufRead::lines()`, but keeps track of each line's original terminator
+// instead of discarding it, so it can be reproduced in preserve mode.
+struct TerminatedLines<'a> {
+    input: &'a mut dyn BufRead
+}
+
+impl<'a> Iterator for TerminatedLines<'a> {
+    type Item = io::Result<(String, Terminator)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = String::new();
+        match self.input.read_line(&mut line) {
+            Ok(0) => None,
+            Ok(_) => {
+                let terminator = if line.ends_with("\r\n") {
+                    line.truncate(line.len() - 2);
+                    Terminator::CrLf
+                } else if line.ends_with('\n') {
+                    line.truncate(line.len() - 1);
+                    Terminator::Lf
+                } else {
+                    Terminator::None
+                };
+                Some(Ok((line, terminator)))
+            },
+            Err(error) => Some(Err(error))
+        }
+    }
+}
+
+fn write_line(output: &mut dyn Write, line: &str, terminator: &Terminator, preserve: bool) -> io::Result<()> {
+    if preserve {
+        write!(output, "{}", line)?;
+        output.write_all(terminator.as_bytes())
+    } else {
+        writeln!(output, "{}", line)
+    }
+}
+
+// Applies a second BEGIN:END[:STEP] slice to `line`'s delimiter-separated
+// fields, rejoining the selected fields with the same delimiter. Reuses the
+// same index resolution as the line- and char/byte-level slices.
+fn select_fields(line: &str, field_slice: &Slice, delimiter: &str) -> String {
+    let fields: Vec<&str> = line.split(delimiter).collect();
+    let (begin, end) = resolve_range(field_slice, fields.len());
+
+    let step = field_slice.step.unwrap_or(1);
+    let stride = step.unsigned_abs();
+
+    let selected: Vec<&str> = if step > 0 {
+        fields[begin..end].iter().copied().step_by(stride).collect()
+    } else {
+        fields[begin..end].iter().copied().rev().step_by(stride).collect()
+    };
+
+    selected.join(delimiter)
+}
+
+fn apply_fields<'a>(line: &'a str, fields: Option<(&Slice, &str)>) -> Cow<'a, str> {
+    match fields {
+        Some((field_slice, delimiter)) => Cow::Owned(select_fields(line, field_slice, delimiter)),
+        None => Cow::Borrowed(line)
+    }
+}
+
+// Writes `line` if its position within the selected window lands on the
+// stride, i.e. every `stride`-th line starting from the window's first one.
+fn emit_strided(output: &mut dyn Write, line: &str, terminator: &Terminator, preserve: bool,
+        fields: Option<(&Slice, &str)>, stride: usize, window_index: usize) -> io::Result<()> {
+    if window_index % stride == 0 {
+        write_line(output, &apply_fields(line, fields), terminator, preserve)
+    } else {
+        Ok(())
+    }
+}
+
+pub fn slice_input(slice: Slice, input: &mut dyn BufRead, output: &mut dyn Write, preserve: bool,
+        fields: Option<(&Slice, &str)>) -> io::Result<()> {
     enum PrintMode { Buf, Overflow };
     let mut mode = PrintMode::Overflow;
 
     let mut buf_size: usize = 0;
 
     let mut skip_count: usize = 0;
-    let mut stop_count: usize = std::usize::MAX;
+    let mut stop_count: usize = usize::MAX;
 
     if slice.begin > 0 {
         skip_count = slice.begin as usize;
@@ -72,24 +200,47 @@ pub fn slice_input(slice: Slice, input: &mut dyn BufRead, output: &mut dyn Write
         buf_size = -slice.begin as usize;
     }
 
-    let mut buf: VecDeque<String> = VecDeque::new();
-    buf.reserve(buf_size);
+    let step = slice.step.unwrap_or(1);
+    let stride = step.unsigned_abs();
+
+    let mut tail_buf: VecDeque<(String, Terminator)> = VecDeque::new();
+    tail_buf.reserve(buf_size);
+
+    // A negative step reads back-to-front, which can't be streamed: the whole
+    // window has to accumulate here before the first line can be emitted in
+    // reverse. A positive step only needs to know each line's position within
+    // the window, so it can be written out as soon as that's known, without
+    // ever holding more than `buf_size` lines at a time.
+    let mut window: VecDeque<(String, Terminator)> = VecDeque::new();
+    let mut window_index: usize = 0;
 
     let mut lines_processed: usize = 0;
-    for maybe_line in input.lines().skip(skip_count).take(stop_count.saturating_add(buf_size)) {
+    for maybe_line in (TerminatedLines{ input }).skip(skip_count).take(stop_count.saturating_add(buf_size)) {
         let line = maybe_line?;
 
         if buf_size == 0 {
-            writeln!(output, "{}", line)?;
+            if step < 0 {
+                window.push_back(line);
+            } else {
+                let (line, terminator) = line;
+                emit_strided(output, &line, &terminator, preserve, fields, stride, window_index)?;
+                window_index += 1;
+            }
         } else {
-            if buf.len() == buf_size {
-                let front = buf.pop_front().unwrap();
+            if tail_buf.len() == buf_size {
+                let front = tail_buf.pop_front().unwrap();
                 if let PrintMode::Overflow = mode {
-                    writeln!(output, "{}", front)?;
+                    if step < 0 {
+                        window.push_back(front);
+                    } else {
+                        let (line, terminator) = front;
+                        emit_strided(output, &line, &terminator, preserve, fields, stride, window_index)?;
+                        window_index += 1;
+                    }
                 }
             }
 
-            buf.push_back(line);
+            tail_buf.push_back(line);
         }
 
         lines_processed += 1;
@@ -98,21 +249,238 @@ pub fn slice_input(slice: Slice, input: &mut dyn BufRead, output: &mut dyn Write
     if let PrintMode::Buf = mode {
         if let Some(slice_end) = slice.end {
             if slice_end < 0 {
-                buf.truncate(buf.len() - cmp::min(buf.len(), -slice_end as usize));
+                tail_buf.truncate(tail_buf.len() - cmp::min(tail_buf.len(), -slice_end as usize));
             } else {
-                buf.truncate(
-                    buf.len() - cmp::min(buf.len(), lines_processed.saturating_sub(stop_count)));
+                tail_buf.truncate(
+                    tail_buf.len() - cmp::min(tail_buf.len(), lines_processed.saturating_sub(stop_count)));
             }
         }
 
-        for line in buf {
-            writeln!(output, "{}", line)?;
+        if step < 0 {
+            window.extend(tail_buf);
+        } else {
+            for (line, terminator) in tail_buf {
+                emit_strided(output, &line, &terminator, preserve, fields, stride, window_index)?;
+                window_index += 1;
+            }
+        }
+    }
+
+    // Only ever reached with a negative step: emit the fully materialized
+    // window back-to-front.
+    for (index, (line, terminator)) in window.into_iter().rev().enumerate() {
+        if index % stride == 0 {
+            write_line(output, &apply_fields(&line, fields), &terminator, preserve)?;
         }
     }
 
     Ok(())
 }
 
+// How many trailing lines a sub-slice might still need once the total line
+// count is known, because it has a negative begin/end anchored to the end of
+// the file. `None` means the sub-slice can only be resolved once the whole
+// file has been read: a negative step anchors its stride at the last line of
+// the resolved window, and with a non-negative begin that window can reach
+// back to any line of the file, so no bounded tail buffer covers it.
+fn slice_lookback(slice: &Slice) -> Option<usize> {
+    if slice.begin >= 0 && slice.step.is_some_and(|step| step < 0) {
+        return None;
+    }
+
+    let mut lookback: usize = 0;
+    if slice.begin < 0 {
+        lookback = cmp::max(lookback, slice.begin.unsigned_abs());
+    }
+    if let Some(end) = slice.end {
+        if end < 0 {
+            lookback = cmp::max(lookback, end.unsigned_abs());
+        }
+    }
+
+    Some(lookback)
+}
+
+// Whether a sub-slice can decide a line as soon as it leaves the tail buffer,
+// without knowing the file's total line count: its begin counts from the
+// start and its step is positive. A negative end is fine here, since a line
+// that has left a buffer of at least |end| lines is inside that end bound.
+fn slice_is_immediate(slice: &Slice) -> bool {
+    slice.begin >= 0 && slice.step.is_none_or(|step| step > 0)
+}
+
+// Whether `index` falls in the resolved `[begin, end)` window at the right
+// stride: forward from `begin` for a positive step, backward from `end` for
+// a negative one (see `slice_input`'s own window/stepping split).
+fn stride_selects(begin: usize, end: usize, step: Option<isize>, index: usize) -> bool {
+    if index < begin || index >= end {
+        return false;
+    }
+
+    let step = step.unwrap_or(1);
+    let stride = step.unsigned_abs();
+    if step > 0 {
+        (index - begin).is_multiple_of(stride)
+    } else {
+        (end - 1 - index).is_multiple_of(stride)
+    }
+}
+
+// A `SliceSet` is a one-pass streaming extractor in the common case: a
+// sub-slice only needs buffering when it has a negative begin/end (anchored
+// to the end of the file, not yet known while streaming). We size a single
+// tail buffer to the largest such lookback across all sub-slices (0 when none
+// are negative) and decide every line as soon as it leaves that buffer. A
+// negative-step sub-slice with a non-negative begin forces the whole file to
+// be buffered, as `slice_input` buffers its whole window for a negative step.
+pub fn slice_input_set(slices: Vec<Slice>, input: &mut dyn BufRead, output: &mut dyn Write, preserve: bool,
+        fields: Option<(&Slice, &str)>) -> io::Result<()> {
+    let tail_size = slices.iter()
+        .map(slice_lookback)
+        .collect::<Option<Vec<usize>>>()
+        .map(|lookbacks| lookbacks.into_iter().max().unwrap_or(0))
+        .unwrap_or(usize::MAX);
+
+    let immediate_slices: Vec<&Slice> = slices.iter().filter(|slice| slice_is_immediate(slice)).collect();
+
+    let emit_if_immediate = |output: &mut dyn Write, index: usize, line: &str, terminator: &Terminator| {
+        let selected = immediate_slices.iter().any(|slice|
+            stride_selects(slice.begin as usize,
+                slice.end.filter(|&end| end >= 0).map_or(usize::MAX, |end| end as usize),
+                slice.step, index));
+        if selected {
+            write_line(output, &apply_fields(line, fields), terminator, preserve)
+        } else {
+            Ok(())
+        }
+    };
+
+    let mut tail_buf: VecDeque<(usize, String, Terminator)> = VecDeque::new();
+    let mut total_lines: usize = 0;
+
+    for maybe_line in (TerminatedLines{ input }) {
+        let (line, terminator) = maybe_line?;
+
+        if tail_size > 0 && tail_buf.len() == tail_size {
+            let (index, line, terminator) = tail_buf.pop_front().unwrap();
+            emit_if_immediate(output, index, &line, &terminator)?;
+        }
+
+        if tail_size == 0 {
+            emit_if_immediate(output, total_lines, &line, &terminator)?;
+        } else {
+            tail_buf.push_back((total_lines, line, terminator));
+        }
+
+        total_lines += 1;
+    }
+
+    // What's left in the tail buffer is the only part of the file any
+    // sub-slice's negative anchors could have selected; resolve them now that
+    // the total line count is known, re-checking every sub-slice (including
+    // the immediate ones) since lines here were never decided above.
+    let mut selected_indices: BTreeSet<usize> = BTreeSet::new();
+    for slice in &slices {
+        let (begin, end) = resolve_range(slice, total_lines);
+        if begin < end {
+            let step = slice.step.unwrap_or(1);
+            let stride = step.unsigned_abs();
+            if step > 0 {
+                selected_indices.extend((begin..end).step_by(stride));
+            } else {
+                let mut offset = 0;
+                while offset < end - begin {
+                    selected_indices.insert(end - 1 - offset);
+                    offset += stride;
+                }
+            }
+        }
+    }
+
+    for (index, line, terminator) in tail_buf {
+        if selected_indices.contains(&index) {
+            write_line(output, &apply_fields(&line, fields), &terminator, preserve)?;
+        }
+    }
+
+    Ok(())
+}
+
+// Resolves a begin/end index (positive counting from the start, negative
+// counting from the end) against a known total length, clamping to [0, len].
+fn resolve_index(value: isize, len: usize) -> usize {
+    if value < 0 {
+        len.saturating_sub(value.unsigned_abs())
+    } else {
+        cmp::min(value as usize, len)
+    }
+}
+
+fn resolve_range(slice: &Slice, len: usize) -> (usize, usize) {
+    let begin = resolve_index(slice.begin, len);
+    let end = match slice.end {
+        Some(end_value) => resolve_index(end_value, len),
+        None => len
+    };
+
+    if end > begin { (begin, end) } else { (begin, begin) }
+}
+
+pub fn slice_input_chars(slice: Slice, input: &mut dyn BufRead, output: &mut dyn Write) -> io::Result<()> {
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+
+    let chars: Vec<char> = text.chars().collect();
+    let (begin, end) = resolve_range(&slice, chars.len());
+
+    let step = slice.step.unwrap_or(1);
+    let stride = step.unsigned_abs();
+
+    let mut selected = String::new();
+    if step > 0 {
+        for (index, ch) in chars[begin..end].iter().enumerate() {
+            if index % stride == 0 {
+                selected.push(*ch);
+            }
+        }
+    } else {
+        for (index, ch) in chars[begin..end].iter().rev().enumerate() {
+            if index % stride == 0 {
+                selected.push(*ch);
+            }
+        }
+    }
+
+    output.write_all(selected.as_bytes())
+}
+
+pub fn slice_input_bytes(slice: Slice, input: &mut dyn BufRead, output: &mut dyn Write) -> io::Result<()> {
+    let mut bytes = Vec::new();
+    input.read_to_end(&mut bytes)?;
+
+    let (begin, end) = resolve_range(&slice, bytes.len());
+
+    let step = slice.step.unwrap_or(1);
+    let stride = step.unsigned_abs();
+
+    let mut selected = Vec::new();
+    if step > 0 {
+        for (index, byte) in bytes[begin..end].iter().enumerate() {
+            if index % stride == 0 {
+                selected.push(*byte);
+            }
+        }
+    } else {
+        for (index, byte) in bytes[begin..end].iter().rev().enumerate() {
+            if index % stride == 0 {
+                selected.push(*byte);
+            }
+        }
+    }
+
+    output.write_all(&selected)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,7 +509,7 @@ mod tests {
 
     #[test]
     fn slice_from_string_colon_two_numbers() {
-        assert!(Slice::from_string(":1:2").is_err());
+        assert_eq!(Slice::from_string(":1:2"), Ok(Slice{ begin: 0, end: Some(1), step: Some(2) }));
     }
 
     #[test]
@@ -156,7 +524,27 @@ mod tests {
 
     #[test]
     fn slice_from_string_colon_three_numbers() {
-        assert!(Slice::from_string("1:2:3").is_err());
+        assert_eq!(Slice::from_string("1:2:3"), Ok(Slice{ begin: 1, end: Some(2), step: Some(3) }));
+    }
+
+    #[test]
+    fn slice_from_string_invalid_step() {
+        assert!(Slice::from_string("1:2:b").is_err());
+    }
+
+    #[test]
+    fn slice_from_string_zero_step() {
+        assert!(Slice::from_string("1:2:0").is_err());
+    }
+
+    #[test]
+    fn slice_from_string_empty_step() {
+        assert!(Slice::from_string("1:2:").is_err());
+    }
+
+    #[test]
+    fn slice_from_string_four_numbers() {
+        assert!(Slice::from_string("1:2:3:4").is_err());
     }
 
     #[test]
@@ -164,44 +552,85 @@ mod tests {
         assert!(Slice::from_string(":").is_err());
     }
 
+    #[test]
+    fn slice_from_string_colon_colon() {
+        assert!(Slice::from_string("::").is_err());
+    }
+
+    #[test]
+    fn slice_from_string_colon_colon_negative() {
+        assert_eq!(Slice::from_string("::-1"), Ok(Slice{ begin: 0, end: None, step: Some(-1) }));
+    }
+
     #[test]
     fn slice_from_string_two_numbers() {
-        assert_eq!(Slice::from_string("1:2"), Ok(Slice{ begin: 1, end: Some(2) }));
+        assert_eq!(Slice::from_string("1:2"), Ok(Slice{ begin: 1, end: Some(2), step: None }));
     }
 
     #[test]
     fn slice_from_string_number_colon() {
-        assert_eq!(Slice::from_string("1:"), Ok(Slice{ begin: 1, end: None }));
+        assert_eq!(Slice::from_string("1:"), Ok(Slice{ begin: 1, end: None, step: None }));
     }
 
     #[test]
     fn slice_from_string_colon_number() {
-        assert_eq!(Slice::from_string(":1"), Ok(Slice{ begin: 0, end: Some(1) }));
+        assert_eq!(Slice::from_string(":1"), Ok(Slice{ begin: 0, end: Some(1), step: None }));
     }
 
     #[test]
     fn slice_from_string_negative_number() {
-        assert_eq!(Slice::from_string("-1:2"), Ok(Slice{ begin: -1, end: Some(2) }));
+        assert_eq!(Slice::from_string("-1:2"), Ok(Slice{ begin: -1, end: Some(2), step: None }));
     }
 
     #[test]
     fn slice_from_string_number_negative() {
-        assert_eq!(Slice::from_string("1:-2"), Ok(Slice{ begin: 1, end: Some(-2) }));
+        assert_eq!(Slice::from_string("1:-2"), Ok(Slice{ begin: 1, end: Some(-2), step: None }));
     }
 
     #[test]
     fn slice_from_string_two_negatives() {
-        assert_eq!(Slice::from_string("-1:-2"), Ok(Slice{ begin: -1, end: Some(-2) }));
+        assert_eq!(Slice::from_string("-1:-2"), Ok(Slice{ begin: -1, end: Some(-2), step: None }));
     }
 
     #[test]
     fn slice_display() {
-        assert_eq!(format!("{}", Slice{ begin: 1, end: Some(2) }), "[1:2]");
+        assert_eq!(format!("{}", Slice{ begin: 1, end: Some(2), step: None }), "[1:2]");
     }
 
     #[test]
     fn slice_display_end_none() {
-        assert_eq!(format!("{}", Slice{ begin: 1, end: None }), "[1:]");
+        assert_eq!(format!("{}", Slice{ begin: 1, end: None, step: None }), "[1:]");
+    }
+
+    #[test]
+    fn slice_display_with_step() {
+        assert_eq!(format!("{}", Slice{ begin: 1, end: Some(2), step: Some(3) }), "[1:2:3]");
+    }
+
+    #[test]
+    fn slice_display_end_none_with_step() {
+        assert_eq!(format!("{}", Slice{ begin: 1, end: None, step: Some(-1) }), "[1::-1]");
+    }
+
+    #[test]
+    fn slice_set_from_string_single_slice() {
+        assert_eq!(SliceSet::from_string("1:3"),
+            Ok(SliceSet{ slices: vec![Slice{ begin: 1, end: Some(3), step: None }] }));
+    }
+
+    #[test]
+    fn slice_set_from_string_multiple_slices() {
+        assert_eq!(SliceSet::from_string("1:3,5:7,-2:"),
+            Ok(SliceSet{ slices: vec![
+                Slice{ begin: 1, end: Some(3), step: None },
+                Slice{ begin: 5, end: Some(7), step: None },
+                Slice{ begin: -2, end: None, step: None }
+            ] }));
+    }
+
+    #[test]
+    fn slice_set_from_string_invalid_sub_slice() {
+        assert!(SliceSet::from_string("1:3,abc").is_err());
     }
 
     // Poor man's parametrized tests
@@ -219,7 +648,17 @@ mod tests {
         let slice = Slice::from_string(slice_str).unwrap();
         let mut in_buf = input.as_bytes();
         let mut out_buf = Vec::new();
-        assert!(slice_input(slice, &mut in_buf, &mut out_buf).is_ok());
+        assert!(slice_input(slice, &mut in_buf, &mut out_buf, false, None).is_ok());
+
+        let output = std::str::from_utf8(&out_buf).unwrap();
+        assert_eq!(output, expected_output);
+    }
+
+    fn check_slice_preserve(slice_str: &str, input: &str, expected_output: &str) {
+        let slice = Slice::from_string(slice_str).unwrap();
+        let mut in_buf = input.as_bytes();
+        let mut out_buf = Vec::new();
+        assert!(slice_input(slice, &mut in_buf, &mut out_buf, true, None).is_ok());
 
         let output = std::str::from_utf8(&out_buf).unwrap();
         assert_eq!(output, expected_output);
@@ -361,4 +800,192 @@ mod tests {
             check_slice(":0", input, "");
         }
     }
+
+    #[test] // positive step over a positive range
+    fn slice_step_positive_over_positive_range() {
+        for input in TEST_INPUTS.iter() {
+            let expected_output =
+                    input
+                        .lines()
+                        .skip(1)
+                        .take(4)
+                        .step_by(2)
+                        .flat_map(|s| s.chars().chain(iter::once('\n')))
+                        .collect::<String>();
+            check_slice("1:5:2", input, &expected_output);
+        }
+    }
+
+    #[test] // negative step, no begin/end: reverse the whole file
+    fn slice_step_negative_reverses_whole_file() {
+        for input in TEST_INPUTS.iter() {
+            let expected_output =
+                    input
+                        .lines()
+                        .rev()
+                        .flat_map(|s| s.chars().chain(iter::once('\n')))
+                        .collect::<String>();
+            check_slice("::-1", input, &expected_output);
+        }
+    }
+
+    #[test] // negative begin combined with negative step
+    fn slice_step_negative_begin_negative_step() {
+        for input in TEST_INPUTS.iter() {
+            let expected_output =
+                    input
+                        .lines()
+                        .rev()
+                        .take(3)
+                        .flat_map(|s| s.chars().chain(iter::once('\n')))
+                        .collect::<String>();
+            check_slice("-3::-1", input, &expected_output);
+        }
+    }
+
+    fn check_slice_chars(slice_str: &str, input: &str, expected_output: &str) {
+        let slice = Slice::from_string(slice_str).unwrap();
+        let mut in_buf = input.as_bytes();
+        let mut out_buf = Vec::new();
+        assert!(slice_input_chars(slice, &mut in_buf, &mut out_buf).is_ok());
+
+        let output = std::str::from_utf8(&out_buf).unwrap();
+        assert_eq!(output, expected_output);
+    }
+
+    fn check_slice_bytes(slice_str: &str, input: &[u8], expected_output: &[u8]) {
+        let slice = Slice::from_string(slice_str).unwrap();
+        let mut in_buf = input;
+        let mut out_buf = Vec::new();
+        assert!(slice_input_bytes(slice, &mut in_buf, &mut out_buf).is_ok());
+
+        assert_eq!(out_buf, expected_output);
+    }
+
+    #[test]
+    fn slice_chars_positive_range() {
+        check_slice_chars("1:4", "abcdef", "bcd");
+    }
+
+    #[test]
+    fn slice_chars_negative_end() {
+        check_slice_chars("5:-3", "hello world", " wo");
+    }
+
+    #[test]
+    fn slice_chars_respects_utf8_boundaries() {
+        // "héllo" is 5 scalar values but 6 bytes ('é' is 2 bytes in UTF-8).
+        check_slice_chars("1:3", "héllo", "él");
+    }
+
+    #[test]
+    fn slice_chars_out_of_range() {
+        check_slice_chars("10:20", "abc", "");
+    }
+
+    #[test]
+    fn slice_bytes_positive_range() {
+        check_slice_bytes("1:4", b"abcdef", b"bcd");
+    }
+
+    #[test]
+    fn slice_bytes_negative_end() {
+        check_slice_bytes("5:-3", b"hello world", b" wo");
+    }
+
+    #[test]
+    fn slice_bytes_out_of_range() {
+        check_slice_bytes("10:20", b"abc", b"");
+    }
+
+    #[test] // preserve mode round-trips CRLF terminators byte-for-byte
+    fn slice_preserve_crlf() {
+        check_slice_preserve("1:3", TEST_INPUTS[3], "def ghi\r\nghi jkl\r\n");
+    }
+
+    #[test] // preserve mode keeps LF terminators when the input already used LF
+    fn slice_preserve_lf() {
+        check_slice_preserve("1:3", TEST_INPUTS[0], "def ghi\nghi jkl\n");
+    }
+
+    #[test] // preserve mode does not invent a trailing newline
+    fn slice_preserve_no_trailing_newline() {
+        check_slice_preserve("3:", "a\nb\nc\nd\ne", "d\ne");
+    }
+
+    #[test] // preserve mode does not invent a trailing newline on CRLF input either
+    fn slice_preserve_no_trailing_newline_crlf() {
+        check_slice_preserve("3:", TEST_INPUTS[5], "d\r\ne");
+    }
+
+    #[test] // default (non-preserve) mode still normalizes CRLF input to LF
+    fn slice_default_normalizes_crlf() {
+        check_slice("1:3", TEST_INPUTS[3], "def ghi\nghi jkl\n");
+    }
+
+    fn check_slice_set(slice_set_str: &str, input: &str, expected_output: &str) {
+        let slice_set = SliceSet::from_string(slice_set_str).unwrap();
+        let mut in_buf = input.as_bytes();
+        let mut out_buf = Vec::new();
+        assert!(slice_input_set(slice_set.slices, &mut in_buf, &mut out_buf, false, None).is_ok());
+
+        let output = std::str::from_utf8(&out_buf).unwrap();
+        assert_eq!(output, expected_output);
+    }
+
+    const SET_TEST_INPUT: &str = "a\nb\nc\nd\ne\nf\ng\n";
+
+    #[test] // disjoint positive ranges stay in input order
+    fn slice_set_disjoint_positive_ranges() {
+        check_slice_set("1:3,5:7", SET_TEST_INPUT, "b\nc\nf\ng\n");
+    }
+
+    #[test] // overlapping ranges collapse to their union, without duplicates
+    fn slice_set_overlapping_ranges_collapse() {
+        check_slice_set("1:4,2:5", SET_TEST_INPUT, "b\nc\nd\ne\n");
+    }
+
+    #[test] // mixing a positive range with a negative, open-ended one
+    fn slice_set_positive_and_negative_open_ended() {
+        check_slice_set("0:2,-2:", SET_TEST_INPUT, "a\nb\nf\ng\n");
+    }
+
+    #[test] // a single sub-slice behaves the same as plain -s
+    fn slice_set_single_slice() {
+        check_slice_set("1:3", SET_TEST_INPUT, "b\nc\n");
+    }
+
+    fn check_slice_fields(slice_str: &str, field_slice_str: &str, delimiter: &str,
+            input: &str, expected_output: &str) {
+        let slice = Slice::from_string(slice_str).unwrap();
+        let field_slice = Slice::from_string(field_slice_str).unwrap();
+        let mut in_buf = input.as_bytes();
+        let mut out_buf = Vec::new();
+        assert!(slice_input(slice, &mut in_buf, &mut out_buf, false, Some((&field_slice, delimiter))).is_ok());
+
+        let output = std::str::from_utf8(&out_buf).unwrap();
+        assert_eq!(output, expected_output);
+    }
+
+    const FIELD_TEST_INPUT: &str = "a,b,c,d\ne,f,g,h\ni,j,k,l\n";
+
+    #[test] // positive field range, applied after a line slice
+    fn slice_fields_positive_range() {
+        check_slice_fields("0:", "1:3", ",", FIELD_TEST_INPUT, "b,c\nf,g\nj,k\n");
+    }
+
+    #[test] // negative field indices count from the end of each line's fields
+    fn slice_fields_negative_range() {
+        check_slice_fields("0:", "-2:", ",", FIELD_TEST_INPUT, "c,d\ng,h\nk,l\n");
+    }
+
+    #[test] // a field slice outside a line's field count prints that line empty, not an error
+    fn slice_fields_out_of_range_is_empty() {
+        check_slice_fields("0:", "5:9", ",", FIELD_TEST_INPUT, "\n\n\n");
+    }
+
+    #[test] // per-line field counts can differ; resolution is recomputed for each line
+    fn slice_fields_varying_field_counts() {
+        check_slice_fields("0:", "-1:", ",", "a,b,c\nd,e\nf\n", "c\ne\nf\n");
+    }
 }
\ No newline at end of file